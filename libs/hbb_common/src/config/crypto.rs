@@ -0,0 +1,137 @@
+use std::{collections::HashMap, path::Path};
+
+use super::ConfigError;
+
+pub(super) const PEER_EXPORT_MAGIC: &[u8] = b"RDPEER1";
+
+pub(super) fn export_key(
+    passphrase: &str,
+    salt: &sodiumoxide::crypto::pwhash::argon2id13::Salt,
+) -> crate::ResultType<sodiumoxide::crypto::secretbox::Key> {
+    use sodiumoxide::crypto::{pwhash::argon2id13, secretbox};
+    let mut key = secretbox::Key([0; secretbox::KEYBYTES]);
+    if argon2id13::derive_key(
+        &mut key.0,
+        passphrase.as_bytes(),
+        salt,
+        argon2id13::OPSLIMIT_INTERACTIVE,
+        argon2id13::MEMLIMIT_INTERACTIVE,
+    )
+    .is_err()
+    {
+        return Err(ConfigError::Encrypt("Failed to derive key".to_owned()).into());
+    }
+    Ok(key)
+}
+
+// magic, salt, nonce, then data sealed with the key derived from the salt
+pub(super) fn seal_with(
+    magic: &[u8],
+    data: &[u8],
+    salt: &sodiumoxide::crypto::pwhash::argon2id13::Salt,
+    key: &sodiumoxide::crypto::secretbox::Key,
+) -> Vec<u8> {
+    use sodiumoxide::crypto::secretbox;
+    let nonce = secretbox::gen_nonce();
+    let mut bytes = magic.to_vec();
+    bytes.extend_from_slice(&salt.0);
+    bytes.extend_from_slice(&nonce.0);
+    bytes.extend(secretbox::seal(data, &nonce, key));
+    bytes
+}
+
+// (salt, nonce, sealed data) of seal_with, None if bytes don't start with magic
+pub(super) fn split_sealed<'a>(
+    magic: &[u8],
+    bytes: &'a [u8],
+) -> Option<(
+    sodiumoxide::crypto::pwhash::argon2id13::Salt,
+    sodiumoxide::crypto::secretbox::Nonce,
+    &'a [u8],
+)> {
+    use sodiumoxide::crypto::{pwhash::argon2id13, secretbox};
+    if !bytes.starts_with(magic) {
+        return None;
+    }
+    let rest = &bytes[magic.len()..];
+    if rest.len() < argon2id13::SALTBYTES + secretbox::NONCEBYTES {
+        return None;
+    }
+    let (salt, rest) = rest.split_at(argon2id13::SALTBYTES);
+    let (nonce, sealed) = rest.split_at(secretbox::NONCEBYTES);
+    Some((
+        argon2id13::Salt::from_slice(salt)?,
+        secretbox::Nonce::from_slice(nonce)?,
+        sealed,
+    ))
+}
+
+pub(super) const FILE_ENCRYPTION_MAGIC: &[u8] = b"RDCFGENC1";
+
+// passphrase of the whole-file encryption, with the keys derived from it by salt,
+// deriving is slow on purpose so each salt is derived once
+pub(super) struct FileEncryption {
+    passphrase: String,
+    salt: sodiumoxide::crypto::pwhash::argon2id13::Salt,
+    keys: HashMap<Vec<u8>, sodiumoxide::crypto::secretbox::Key>,
+}
+
+impl FileEncryption {
+    pub(super) fn new(passphrase: &str) -> crate::ResultType<Self> {
+        let salt = sodiumoxide::crypto::pwhash::argon2id13::gen_salt();
+        let key = export_key(passphrase, &salt)?;
+        Ok(Self {
+            passphrase: passphrase.to_owned(),
+            keys: HashMap::from([(salt.0.to_vec(), key)]),
+            salt,
+        })
+    }
+
+    pub(super) fn key(
+        &mut self,
+        salt: &sodiumoxide::crypto::pwhash::argon2id13::Salt,
+    ) -> crate::ResultType<sodiumoxide::crypto::secretbox::Key> {
+        if let Some(key) = self.keys.get(&salt.0[..]) {
+            return Ok(key.clone());
+        }
+        let key = export_key(&self.passphrase, salt)?;
+        self.keys.insert(salt.0.to_vec(), key.clone());
+        Ok(key)
+    }
+
+    pub(super) fn seal(&mut self, data: &[u8]) -> crate::ResultType<Vec<u8>> {
+        let salt = self.salt;
+        let key = self.key(&salt)?;
+        Ok(seal_with(FILE_ENCRYPTION_MAGIC, data, &salt, &key))
+    }
+}
+
+// data as is if it is not encrypted
+pub(super) fn open_file_data(
+    encryption: Option<&mut FileEncryption>,
+    file: &Path,
+    data: Vec<u8>,
+) -> crate::ResultType<Vec<u8>> {
+    let (salt, nonce, sealed) = match split_sealed(FILE_ENCRYPTION_MAGIC, &data) {
+        Some(parts) => parts,
+        None => return Ok(data),
+    };
+    let encryption = match encryption {
+        Some(encryption) => encryption,
+        None => {
+            return Err(ConfigError::Encrypt(format!(
+                "{} is encrypted, no passphrase is set",
+                file.display()
+            ))
+            .into())
+        }
+    };
+    let key = encryption.key(&salt)?;
+    sodiumoxide::crypto::secretbox::open(sealed, &nonce, &key).map_err(|_| {
+        ConfigError::Encrypt(format!(
+            "{}: wrong passphrase or corrupted data",
+            file.display()
+        ))
+        .into()
+    })
+}
@@ -0,0 +1,135 @@
+use super::RELAY_PORT;
+
+// separated by commas, semicolons or whitespace, invalid entries and duplicates dropped
+pub(super) fn parse_servers(s: &str) -> Vec<String> {
+    let mut servers: Vec<String> = Vec::new();
+    for x in s.split(|c: char| c == ',' || c == ';' || c.is_whitespace()) {
+        if is_valid_host_port(x) && !servers.iter().any(|s| s == x) {
+            servers.push(x.to_owned());
+        }
+    }
+    servers
+}
+
+// host, host:port, ip, ip:port or [v6]:port
+fn is_valid_host_port(s: &str) -> bool {
+    let host = strip_port(s);
+    let port = if let Some(rest) = s.strip_prefix('[') {
+        match rest.split_once(']') {
+            Some((_, "")) => None,
+            Some((_, p)) => match p.strip_prefix(':') {
+                Some(p) => Some(p),
+                None => return false,
+            },
+            None => return false,
+        }
+    } else if host.len() != s.len() {
+        s.rsplit(':').next()
+    } else {
+        None
+    };
+    if let Some(port) = port {
+        if port.parse::<u16>().map_or(true, |p| p == 0) {
+            return false;
+        }
+    }
+    if host.parse::<std::net::IpAddr>().is_ok() {
+        return true;
+    }
+    !host.is_empty()
+        && host.len() <= 253
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+}
+
+// host with port appended unless it already has one
+fn with_port(host: &str, port: i32) -> String {
+    let host = host.trim();
+    let bare = strip_port(host);
+    if bare.len() != host.len() {
+        host.to_owned()
+    } else if bare.contains(':') {
+        format!("[{}]:{}", bare, port)
+    } else {
+        format!("{}:{}", bare, port)
+    }
+}
+
+// the relay-server option, or the rendezvous host with the port next to its rendezvous port,
+// which is RELAY_PORT for RENDEZVOUS_PORT
+pub(super) fn relay_server_for(relay_server: &str, rendezvous_server: &str) -> String {
+    if !relay_server.trim().is_empty() {
+        return with_port(relay_server, RELAY_PORT);
+    }
+    let rendezvous_server = rendezvous_server.trim();
+    let host = strip_port(rendezvous_server);
+    let port = if host.len() != rendezvous_server.len() {
+        rendezvous_server
+            .rsplit(':')
+            .next()
+            .and_then(|x| x.parse::<u16>().ok())
+            .map_or(RELAY_PORT, |x| x as i32 + 1)
+    } else {
+        RELAY_PORT
+    };
+    with_port(host, port)
+}
+
+// "host:port", "[v6]:port" or a bare host/ip
+pub(super) fn strip_port(host: &str) -> &str {
+    let host = host.trim();
+    if let Some(rest) = host.strip_prefix('[') {
+        return rest.split(']').next().unwrap_or(rest);
+    }
+    if host.parse::<std::net::IpAddr>().is_ok() {
+        return host;
+    }
+    match host.rsplit_once(':') {
+        Some((h, port)) if port.parse::<u16>().is_ok() => h,
+        _ => host,
+    }
+}
+
+pub(super) fn bypass_matches(pattern: &str, host: &str) -> bool {
+    use std::net::IpAddr;
+    if pattern == "*" {
+        return true;
+    }
+    if let Some((net, prefix)) = pattern.split_once('/') {
+        let (net, prefix, ip) = match (
+            net.parse::<IpAddr>(),
+            prefix.parse::<u32>(),
+            host.parse::<IpAddr>(),
+        ) {
+            (Ok(net), Ok(prefix), Ok(ip)) => (net, prefix, ip),
+            _ => return false,
+        };
+        return match (net, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) if prefix <= 32 => {
+                let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+                u32::from(net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) if prefix <= 128 => {
+                let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        };
+    }
+    let host = host.to_lowercase();
+    let pattern = pattern.to_lowercase();
+    if let Some(suffix) = pattern.strip_prefix("*.") {
+        return host.ends_with(&format!(".{}", suffix));
+    }
+    if let Some(suffix) = pattern.strip_prefix('.') {
+        return host == suffix || host.ends_with(&pattern);
+    }
+    host == pattern
+}
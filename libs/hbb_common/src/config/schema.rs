@@ -0,0 +1,133 @@
+// (struct, field, type, default, secret) of every stored field, keep in sync with the structs,
+// test_schema_json checks it against them and against PEER_OPTION_DEFAULTS
+pub(super) const SCHEMA: &[(&str, &str, &str, &str, bool)] = &[
+    ("Config", "id", "string", "", false),
+    ("Config", "enc_id", "string", "", true),
+    ("Config", "password", "string", "", true),
+    ("Config", "password2", "string", "", true),
+    ("Config", "salt", "string", "", true),
+    ("Config", "key_pair", "[bytes, bytes]", "[[], []]", true),
+    ("Config", "key_confirmed", "bool", "false", false),
+    ("Config", "written_by", "string", "", false),
+    ("Config", "keys_confirmed", "map<string, bool>", "{}", false),
+    ("Config", "host_key_fingerprints", "map<string, string>", "{}", false),
+    ("Config2", "rendezvous_server", "string", "", false),
+    ("Config2", "nat_type", "i32", "0", false),
+    ("Config2", "serial", "i32", "0", false),
+    ("Config2", "active_proxy", "string?", "", false),
+    ("Config2", "last_relay_server", "string", "", false),
+    ("Config2", "written_by", "string", "", false),
+    ("Config2", "peer_allowlist", "[string]", "[]", false),
+    ("Config2", "peer_denylist", "[string]", "[]", false),
+    ("Config2", "socks", "Socks5Server?", "", true),
+    ("Config2", "proxies", "map<string, Socks5Server>", "{}", true),
+    ("Config2", "blocked_ids", "map<string, SystemTime>", "{}", false),
+    ("Config2", "options_modified", "map<string, SystemTime>", "{}", false),
+    ("Config2", "options", "map<string, string>", "{}", false),
+    ("PeerConfig", "password", "bytes", "[]", true),
+    ("PeerConfig", "size", "[i32; 4]", "[0, 0, 0, 0]", false),
+    ("PeerConfig", "size_ft", "[i32; 4]", "[0, 0, 0, 0]", false),
+    ("PeerConfig", "size_pf", "[i32; 4]", "[0, 0, 0, 0]", false),
+    ("PeerConfig", "view_style", "string", "original", false),
+    ("PeerConfig", "scroll_style", "string", "scrollauto", false),
+    ("PeerConfig", "image_quality", "string", "balanced", false),
+    ("PeerConfig", "custom_image_quality", "[i32]", "[]", false),
+    ("PeerConfig", "show_remote_cursor", "bool", "false", false),
+    ("PeerConfig", "lock_after_session_end", "bool", "false", false),
+    ("PeerConfig", "privacy_mode", "bool", "false", false),
+    ("PeerConfig", "port_forwards", "[(i32, string, i32)]", "[]", false),
+    ("PeerConfig", "direct_failures", "i32", "0", false),
+    ("PeerConfig", "disable_audio", "bool", "false", false),
+    ("PeerConfig", "disable_clipboard", "bool", "false", false),
+    ("PeerConfig", "enable_file_transfer", "bool", "false", false),
+    ("PeerConfig", "show_quality_monitor", "bool", "false", false),
+    ("PeerConfig", "keyboard_mode", "string", "", false),
+    ("PeerConfig", "auto_connect", "bool", "false", false),
+    ("PeerConfig", "auto_connect_order", "i32", "0", false),
+    ("PeerConfig", "connect_count", "u32", "0", false),
+    ("PeerConfig", "last_used", "SystemTime?", "", false),
+    ("PeerConfig", "last_connected", "SystemTime?", "", false),
+    ("PeerConfig", "created", "SystemTime?", "", false),
+    ("PeerConfig", "options", "map<string, string>", "{codec-preference = auto}", false),
+    ("PeerConfig", "ui_flutter", "map<string, string>", "{}", false),
+    ("PeerConfig", "info", "PeerInfoSerde", "{}", false),
+    ("PeerConfig", "transfer", "TransferSerde", "{}", false),
+    ("LocalConfig", "remote_id", "string", "", false),
+    ("LocalConfig", "size", "[i32; 4]", "[0, 0, 0, 0]", false),
+    ("LocalConfig", "written_by", "string", "", false),
+    ("LocalConfig", "fav", "[string]", "[]", false),
+    ("LocalConfig", "options", "map<string, string>", "{}", false),
+    ("LocalConfig", "ui_flutter", "map<string, string>", "{}", false),
+    ("LocalConfig", "fav_meta", "map<string, FavMeta>", "{}", false),
+    ("LocalConfig", "sizes", "map<string, [i32; 4]>", "{}", false),
+    ("LocalConfig", "profiles", "map<string, ConnectionProfile>", "{}", false),
+];
+
+pub(super) const REDACTED: &str = "<redacted>";
+
+// secret fields of SCHEMA, only the passwords of a secret table such as socks
+pub(super) fn redact_secrets(name: &str, table: &mut toml::value::Table) {
+    fn redact_passwords(table: &mut toml::value::Table) {
+        for (k, v) in table.iter_mut() {
+            match v {
+                toml::Value::Table(t) => redact_passwords(t),
+                _ if k == "password" => *v = toml::Value::String(REDACTED.to_owned()),
+                _ => {}
+            }
+        }
+    }
+    for (k, v) in table.iter_mut() {
+        if !SCHEMA.iter().any(|x| x.0 == name && x.1 == k.as_str() && x.4) {
+            continue;
+        }
+        match v {
+            toml::Value::Table(t) => redact_passwords(t),
+            _ => *v = toml::Value::String(REDACTED.to_owned()),
+        }
+    }
+}
+
+pub(super) fn json_str(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// {"Config": [{"name": .., "type": .., "default": .., "secret": ..}, ..], ..}
+pub(super) fn schema_json() -> String {
+    let mut structs: Vec<&str> = Vec::new();
+    for (name, ..) in SCHEMA {
+        if !structs.contains(name) {
+            structs.push(*name);
+        }
+    }
+    let structs: Vec<String> = structs
+        .iter()
+        .map(|name| {
+            let fields: Vec<String> = SCHEMA
+                .iter()
+                .filter(|x| x.0 == *name)
+                .map(|(_, field, ty, default, secret)| {
+                    format!(
+                        "{{\"name\":{},\"type\":{},\"default\":{},\"secret\":{}}}",
+                        json_str(field),
+                        json_str(ty),
+                        json_str(default),
+                        secret
+                    )
+                })
+                .collect();
+            format!("{}:[{}]", json_str(name), fields.join(","))
+        })
+        .collect();
+    format!("{{{}}}", structs.join(","))
+}
@@ -0,0 +1,55 @@
+// quoted if it contains a separator, quote or line break
+pub(super) fn csv_field(s: &str) -> String {
+    if s.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+// comma-separated option value, entries trimmed and empty ones dropped
+pub(super) fn split_list(s: &str) -> Vec<String> {
+    s.split(',')
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty())
+        .map(|x| x.to_owned())
+        .collect()
+}
+
+pub(super) fn join_list(list: &[String]) -> String {
+    list.iter()
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// "1500" or "1500ms" milliseconds, "30s" seconds, "5m" minutes, "2h" hours
+pub(super) fn parse_duration(s: &str) -> Option<std::time::Duration> {
+    let s = s.trim().to_lowercase();
+    let (n, unit_ms) = if let Some(n) = s.strip_suffix("ms") {
+        (n, 1)
+    } else if let Some(n) = s.strip_suffix('s') {
+        (n, 1_000)
+    } else if let Some(n) = s.strip_suffix('m') {
+        (n, 60_000)
+    } else if let Some(n) = s.strip_suffix('h') {
+        (n, 3_600_000)
+    } else {
+        (s.as_str(), 1)
+    };
+    let n: u64 = n.trim().parse().ok()?;
+    n.checked_mul(unit_ms).map(std::time::Duration::from_millis)
+}
+
+// timeout option k in ms, default if absent, not a number or not positive
+pub(super) fn parse_timeout(k: &str, v: Option<&str>, default: u64) -> u64 {
+    match v.map(|v| v.trim().parse::<u64>()) {
+        Some(Ok(v)) if v > 0 => v,
+        Some(_) => {
+            log::warn!("Invalid {}, use default {}", k, default);
+            default
+        }
+        None => default,
+    }
+}
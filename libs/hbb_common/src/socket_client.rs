@@ -18,7 +18,7 @@ fn to_socket_addr(host: &str) -> ResultType<SocketAddr> {
 }
 
 pub fn get_target_addr(host: &str) -> ResultType<TargetAddr<'static>> {
-    let addr = match Config::get_network_type() {
+    let addr = match Config::get_network_type_for(host) {
         NetworkType::Direct => to_socket_addr(&host)?.into_target_addr()?,
         NetworkType::ProxySocks => host.into_target_addr()?,
     }
@@ -50,8 +50,12 @@ pub async fn connect_tcp<'t, T: IntoTargetAddr<'t>>(
     ms_timeout: u64,
 ) -> ResultType<FramedStream> {
     let target_addr = target.into_target_addr()?;
+    let bypass = match &target_addr {
+        TargetAddr::Ip(addr) => Config::should_bypass_proxy(&addr.ip().to_string()),
+        TargetAddr::Domain(domain, _) => Config::should_bypass_proxy(domain),
+    };
 
-    if let Some(conf) = Config::get_socks() {
+    if let Some(conf) = Config::get_socks().filter(|_| !bypass) {
         FramedStream::connect(
             conf.proxy.as_str(),
             target_addr,
@@ -21,7 +21,7 @@ use hbb_common::{
 };
 #[cfg(any(feature = "hwcodec", feature = "mediacodec"))]
 use hbb_common::{
-    config::{Config2, PeerConfig},
+    config::{CodecPreference, Config2, PeerConfig},
     lazy_static,
     message_proto::video_codec_state::PerferCodec,
 };
@@ -406,18 +406,11 @@ impl Decoder {
 
     #[cfg(any(feature = "hwcodec", feature = "mediacodec"))]
     fn codec_preference(id: &str) -> PerferCodec {
-        let codec = PeerConfig::load(id)
-            .options
-            .get("codec-preference")
-            .map_or("".to_owned(), |c| c.to_owned());
-        if codec == "vp9" {
-            PerferCodec::VPX
-        } else if codec == "h264" {
-            PerferCodec::H264
-        } else if codec == "h265" {
-            PerferCodec::H265
-        } else {
-            PerferCodec::Auto
+        match PeerConfig::load(id).codec_preference() {
+            CodecPreference::VP9 => PerferCodec::VPX,
+            CodecPreference::H264 => PerferCodec::H264,
+            CodecPreference::H265 => PerferCodec::H265,
+            _ => PerferCodec::Auto,
         }
     }
 }
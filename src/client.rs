@@ -20,8 +20,7 @@ use hbb_common::{
     anyhow::{anyhow, Context},
     bail,
     config::{
-        Config, PeerConfig, PeerInfoSerde, CONNECT_TIMEOUT, READ_TIMEOUT, RELAY_PORT,
-        RENDEZVOUS_TIMEOUT,
+        host_key_fingerprint, Config, KeyVerdict, PeerConfig, PeerInfoSerde, RELAY_PORT,
     },
     log,
     message_proto::{option_message::BoolOption, *},
@@ -168,12 +167,13 @@ impl Client {
     ) -> ResultType<(Stream, bool)> {
         // to-do: remember the port for each peer, so that we can retry easier
         let any_addr = Config::get_any_listen_addr();
+        let rendezvous_timeout = Config::rendezvous_timeout();
         if crate::is_ip(peer) {
             return Ok((
                 socket_client::connect_tcp(
                     crate::check_port(peer, RELAY_PORT + 1),
                     any_addr,
-                    RENDEZVOUS_TIMEOUT,
+                    rendezvous_timeout,
                 )
                 .await?,
                 true,
@@ -181,12 +181,12 @@ impl Client {
         }
         let (mut rendezvous_server, servers, contained) = crate::get_rendezvous_server(1_000).await;
         let mut socket =
-            socket_client::connect_tcp(&*rendezvous_server, any_addr, RENDEZVOUS_TIMEOUT).await;
+            socket_client::connect_tcp(&*rendezvous_server, any_addr, rendezvous_timeout).await;
         debug_assert!(!servers.contains(&rendezvous_server));
         if socket.is_err() && !servers.is_empty() {
             log::info!("try the other servers: {:?}", servers);
             for server in servers {
-                socket = socket_client::connect_tcp(&*server, any_addr, RENDEZVOUS_TIMEOUT).await;
+                socket = socket_client::connect_tcp(&*server, any_addr, rendezvous_timeout).await;
                 if socket.is_ok() {
                     rendezvous_server = server;
                     break;
@@ -345,7 +345,7 @@ impl Client {
             connect_timeout = MIN;
         } else {
             if relay_server.is_empty() {
-                connect_timeout = CONNECT_TIMEOUT;
+                connect_timeout = Config::connect_timeout();
             } else {
                 if peer_nat_type == NatType::ASYMMETRIC {
                     let mut my_nat_type = my_nat_type;
@@ -353,7 +353,7 @@ impl Client {
                         my_nat_type = crate::get_nat_type(100).await;
                     }
                     if my_nat_type == NatType::ASYMMETRIC as i32 {
-                        connect_timeout = CONNECT_TIMEOUT;
+                        connect_timeout = Config::connect_timeout();
                         if direct_failures > 0 {
                             connect_timeout = punch_time_used * 6;
                         }
@@ -377,16 +377,27 @@ impl Client {
         let mut direct = !conn.is_err();
         if interface.is_force_relay() || conn.is_err() {
             if !relay_server.is_empty() {
-                conn = Self::request_relay(
-                    peer_id,
-                    relay_server.to_owned(),
-                    rendezvous_server,
-                    !signed_id_pk.is_empty(),
-                    key,
-                    token,
-                    conn_type,
-                )
-                .await;
+                // the relay of the last relayed session first, it worked before
+                let mut relays = vec![relay_server.to_owned()];
+                let last_relay = Config::get_last_relay_server();
+                if !last_relay.is_empty() && last_relay != relay_server {
+                    relays.insert(0, last_relay);
+                }
+                for relay in relays {
+                    conn = Self::request_relay(
+                        peer_id,
+                        relay,
+                        rendezvous_server,
+                        !signed_id_pk.is_empty(),
+                        key,
+                        token,
+                        conn_type,
+                    )
+                    .await;
+                    if conn.is_ok() {
+                        break;
+                    }
+                }
                 if conn.is_err() {
                     bail!(
                         "Failed to connect via relay server: {}",
@@ -407,6 +418,7 @@ impl Client {
         let mut conn = conn?;
         log::info!("{:?} used to establish connection", start.elapsed());
         Self::secure_connection(peer_id, signed_id_pk, key, &mut conn, direct, interface).await?;
+        PeerConfig::record_connection(peer_id);
         Ok((conn, direct))
     }
 
@@ -443,7 +455,16 @@ impl Client {
                 return Ok(());
             }
         };
-        match timeout(READ_TIMEOUT, conn.next()).await? {
+        let fingerprint = host_key_fingerprint(&sign_pk.0);
+        if Config::verify_host_key(peer_id, &fingerprint) == KeyVerdict::Changed {
+            interface.msgbox(
+                "custom-nocancel",
+                "Warning",
+                "The public key of the remote desktop changed since the first connection",
+                "",
+            );
+        }
+        match timeout(Config::read_timeout(), conn.next()).await? {
             Some(res) => {
                 let bytes = match res {
                     Ok(bytes) => bytes,
@@ -467,7 +488,7 @@ impl Client {
                                     symmetric_value: sealed_key.into(),
                                     ..Default::default()
                                 });
-                                timeout(CONNECT_TIMEOUT, conn.send(&msg_out)).await??;
+                                timeout(Config::connect_timeout(), conn.send(&msg_out)).await??;
                                 conn.set_key(key);
                             } else {
                                 log::error!("Handshake failed: sign failure");
@@ -511,10 +532,13 @@ impl Client {
         let mut uuid = "".to_owned();
         for i in 1..=3 {
             // use different socket due to current hbbs implement requiring different nat address for each attempt
-            let mut socket =
-                socket_client::connect_tcp(rendezvous_server, any_addr, RENDEZVOUS_TIMEOUT)
-                    .await
-                    .with_context(|| "Failed to connect to rendezvous server")?;
+            let mut socket = socket_client::connect_tcp(
+                rendezvous_server,
+                any_addr,
+                Config::rendezvous_timeout(),
+            )
+            .await
+            .with_context(|| "Failed to connect to rendezvous server")?;
 
             let mut msg_out = RendezvousMessage::new();
             uuid = Uuid::new_v4().to_string();
@@ -535,7 +559,7 @@ impl Client {
                 ..Default::default()
             });
             socket.send(&msg_out).await?;
-            if let Some(Ok(bytes)) = socket.next_timeout(CONNECT_TIMEOUT).await {
+            if let Some(Ok(bytes)) = socket.next_timeout(Config::connect_timeout()).await {
                 if let Ok(msg_in) = RendezvousMessage::parse_from_bytes(&bytes) {
                     if let Some(rendezvous_message::Union::RelayResponse(rs)) = msg_in.union {
                         if !rs.refuse_reason.is_empty() {
@@ -561,13 +585,18 @@ impl Client {
         key: &str,
         conn_type: ConnType,
     ) -> ResultType<Stream> {
-        let mut conn = socket_client::connect_tcp(
-            crate::check_port(relay_server, RELAY_PORT),
+        let res = socket_client::connect_tcp(
+            crate::check_port(&relay_server, RELAY_PORT),
             Config::get_any_listen_addr(),
-            CONNECT_TIMEOUT,
+            Config::connect_timeout(),
         )
-        .await
-        .with_context(|| "Failed to connect to relay server")?;
+        .await;
+        if res.is_ok() {
+            Config::set_last_relay_server(&relay_server);
+        } else {
+            Config::clear_last_relay_server(&relay_server);
+        }
+        let mut conn = res.with_context(|| "Failed to connect to relay server")?;
         let mut msg_out = RendezvousMessage::new();
         msg_out.set_request_relay(RequestRelay {
             licence_key: key.to_owned(),
@@ -56,6 +56,14 @@ pub fn global_init() -> bool {
             crate::server::wayland::set_wayland_scrap_map_err();
         }
     }
+    Config::set_managed_policy_fetcher(Box::new(|url| {
+        Ok(reqwest::blocking::Client::new()
+            .get(url)
+            .timeout(std::time::Duration::from_secs(12))
+            .send()?
+            .error_for_status()?
+            .text()?)
+    }));
     true
 }
 
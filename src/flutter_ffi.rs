@@ -819,7 +819,9 @@ pub fn main_device_name(name: String) {
 }
 
 pub fn main_remove_peer(id: String) {
-    PeerConfig::remove(&id);
+    if let Err(err) = PeerConfig::remove(&id) {
+        log::error!("Failed to remove peer {}: {}", id, err);
+    }
 }
 
 pub fn main_has_hwcodec() -> SyncReturn<bool> {
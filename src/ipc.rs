@@ -347,9 +347,9 @@ async fn handle(data: Data, stream: &mut Connection) {
             }
             Some(data) => {
                 if data.proxy.is_empty() {
-                    Config::set_socks(None);
+                    allow_err!(Config::set_socks(None));
                 } else {
-                    Config::set_socks(Some(data));
+                    allow_err!(Config::set_socks(Some(data)));
                 }
                 crate::common::test_nat_type();
                 RendezvousMediator::restart();
@@ -784,7 +784,7 @@ async fn get_socks_(ms_timeout: u64) -> ResultType<Option<config::Socks5Server>>
     let mut c = connect(ms_timeout, "").await?;
     c.send(&Data::Socks(None)).await?;
     if let Some(Data::Socks(value)) = c.next_timeout(ms_timeout).await? {
-        Config::set_socks(value.clone());
+        allow_err!(Config::set_socks(value.clone()));
         Ok(value)
     } else {
         Ok(Config::get_socks())
@@ -806,7 +806,7 @@ pub async fn set_socks(value: config::Socks5Server) -> ResultType<()> {
         None
     } else {
         Some(value.clone())
-    });
+    })?;
     connect(1_000, "")
         .await?
         .send(&Data::Socks(Some(value)))
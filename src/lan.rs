@@ -19,6 +19,8 @@ use std::{
 
 type Message = RendezvousMessage;
 
+const LAN_PEER_MAX_AGE: std::time::Duration = std::time::Duration::from_secs(30 * 24 * 3600);
+
 pub(super) fn start_listening() -> ResultType<()> {
     let addr = SocketAddr::from(([0, 0, 0, 0], get_broadcast_port()));
     let socket = std::net::UdpSocket::bind(addr)?;
@@ -224,6 +226,7 @@ fn wait_response(
                                     hostname: p.hostname.clone(),
                                     platform: p.platform.clone(),
                                     online: true,
+                                    last_seen: Some(std::time::SystemTime::now()),
                                 }));
                             }
                         }
@@ -260,21 +263,16 @@ async fn handle_received_peers(mut rx: UnboundedReceiver<config::DiscoveryPeer>)
         peer.online = false;
     });
 
+    // one machine may answer under several usernames
+    let by_mac = config::Config::get_option("lan-discovery-match-mac") != "N";
     let mut response_set = HashSet::new();
     let mut last_write_time = Instant::now() - std::time::Duration::from_secs(4);
     loop {
         tokio::select! {
             data = rx.recv() => match data {
-                Some(mut peer) => {
+                Some(peer) => {
                     let in_response_set = !response_set.insert(peer.id.clone());
-                    if let Some(pos) = peers.iter().position(|x| x.is_same_peer(&peer) ) {
-                        let peer1 = peers.remove(pos);
-                        if in_response_set {
-                            peer.ip_mac.extend(peer1.ip_mac);
-                            peer.online = true;
-                        }
-                    }
-                    peers.insert(0, peer);
+                    config::LanPeers::merge(&mut peers, peer, in_response_set, by_mac);
                     if last_write_time.elapsed().as_millis() > 300 {
                         config::LanPeers::store(&peers);
                         #[cfg(feature = "flutter")]
@@ -289,6 +287,7 @@ async fn handle_received_peers(mut rx: UnboundedReceiver<config::DiscoveryPeer>)
         }
     }
 
+    config::LanPeers::prune_stale(&mut peers, LAN_PEER_MAX_AGE);
     config::LanPeers::store(&peers);
     #[cfg(feature = "flutter")]
     crate::flutter_ffi::main_load_lan_peers();
@@ -419,7 +419,9 @@ pub fn is_installed_lower_version() -> bool {
 pub fn closing(x: i32, y: i32, w: i32, h: i32) {
     #[cfg(not(any(target_os = "android", target_os = "ios")))]
     crate::server::input_service::fix_key_down_timeout_at_exit();
-    LocalConfig::set_size(x, y, w, h);
+    LocalConfig::set_size(x, y, w, h, None);
+    // the process exits before the delayed store would run
+    LocalConfig::flush_size();
 }
 
 #[inline]
@@ -516,7 +518,7 @@ pub fn get_icon() -> String {
 
 #[inline]
 pub fn remove_peer(id: String) {
-    PeerConfig::remove(&id);
+    allow_err!(PeerConfig::remove(&id));
 }
 
 #[inline]